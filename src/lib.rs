@@ -0,0 +1,17 @@
+//! Quasi-quoting macro `quote!(...)` for procedural macros.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+extern crate proc_macro2;
+
+// `tokens` provides `Tokens`, the token-buffer type that `to_tokens` and
+// `spanless_eq` build on; it lives alongside these modules in the full crate.
+mod to_tokens;
+mod spanless_eq;
+
+pub use to_tokens::*;
+pub use spanless_eq::*;
@@ -1,6 +1,13 @@
 use tokens::*;
 use to_tokens::ToTokens;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use proc_macro2::{TokenNode, TokenStream, TokenTree};
+
 pub trait SpanlessEq {
     fn spanless_eq(a: &Self, b: &Self) -> bool;
 }
@@ -16,3 +23,39 @@ pub fn spanless_eq<T>(a: &T, b: &T) -> bool
 
     SpanlessEq::spanless_eq(&ta, &tb)
 }
+
+impl SpanlessEq for Tokens {
+    fn spanless_eq(a: &Self, b: &Self) -> bool {
+        let a: TokenStream = a.clone().into();
+        let b: TokenStream = b.clone().into();
+        spanless_eq_stream(&a, &b)
+    }
+}
+
+fn spanless_eq_stream(a: &TokenStream, b: &TokenStream) -> bool {
+    let a: Vec<TokenTree> = a.clone().into_iter().collect();
+    let b: Vec<TokenTree> = b.clone().into_iter().collect();
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(&b).all(|(a, b)| spanless_eq_tree(a, b))
+}
+
+// Compares everything about a `TokenTree` except its `span`.
+fn spanless_eq_tree(a: &TokenTree, b: &TokenTree) -> bool {
+    match (&a.kind, &b.kind) {
+        (&TokenNode::Group(delim_a, ref stream_a), &TokenNode::Group(delim_b, ref stream_b)) => {
+            delim_a == delim_b && spanless_eq_stream(stream_a, stream_b)
+        }
+        (&TokenNode::Term(a), &TokenNode::Term(b)) => a.as_str() == b.as_str(),
+        (&TokenNode::Op(ch_a, spacing_a), &TokenNode::Op(ch_b, spacing_b)) => {
+            ch_a == ch_b && spacing_a == spacing_b
+        }
+        // `proc_macro2::Literal` has no `PartialEq` impl, so fall back to
+        // comparing the tokens they print as.
+        (&TokenNode::Literal(ref a), &TokenNode::Literal(ref b)) => a.to_string() == b.to_string(),
+        _ => false,
+    }
+}
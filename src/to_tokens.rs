@@ -1,8 +1,28 @@
 use super::Tokens;
 
+#[cfg(feature = "std")]
 use std::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::borrow::{Cow, ToOwned};
 
-use proc_macro2::{TokenNode, Literal, Spacing, Delimiter, Term, TokenTree, Span};
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
+use proc_macro2::{TokenNode, Literal, Spacing, Delimiter, Term, TokenTree, TokenStream, Span};
 
 fn tt(kind: TokenNode) -> TokenTree {
     TokenTree {
@@ -46,6 +66,16 @@ pub trait ToTokens {
         self.to_tokens(&mut tokens);
         tokens
     }
+
+    /// Convert `self` into a `Tokens` object without consuming it.
+    ///
+    /// This is the non-consuming counterpart to `into_tokens`, for callers
+    /// that only have a borrow of `self`.
+    fn to_token_stream(&self) -> Tokens {
+        let mut tokens = Tokens::new();
+        self.to_tokens(&mut tokens);
+        tokens
+    }
 }
 
 impl<'a, T: ?Sized + ToTokens> ToTokens for &'a T {
@@ -54,6 +84,12 @@ impl<'a, T: ?Sized + ToTokens> ToTokens for &'a T {
     }
 }
 
+impl<'a, T: ?Sized + ToTokens> ToTokens for &'a mut T {
+    fn to_tokens(&self, tokens: &mut Tokens) {
+        (**self).to_tokens(tokens);
+    }
+}
+
 impl<'a, T: ?Sized + ToOwned + ToTokens> ToTokens for Cow<'a, T> {
     fn to_tokens(&self, tokens: &mut Tokens) {
         (**self).to_tokens(tokens);
@@ -66,6 +102,18 @@ impl<T: ?Sized + ToTokens> ToTokens for Box<T> {
     }
 }
 
+impl<T: ?Sized + ToTokens> ToTokens for Rc<T> {
+    fn to_tokens(&self, tokens: &mut Tokens) {
+        (**self).to_tokens(tokens);
+    }
+}
+
+impl<T: ?Sized + ToTokens> ToTokens for Arc<T> {
+    fn to_tokens(&self, tokens: &mut Tokens) {
+        (**self).to_tokens(tokens);
+    }
+}
+
 impl<T: ToTokens> ToTokens for Option<T> {
     fn to_tokens(&self, tokens: &mut Tokens) {
         if let Some(ref t) = *self {
@@ -74,12 +122,92 @@ impl<T: ToTokens> ToTokens for Option<T> {
     }
 }
 
+/// Interpolates `T`, stamping every token it produces — including tokens
+/// nested inside groups — with the given `Span`.
+///
+/// This is useful for giving interpolated values call-site (or other
+/// explicit) hygiene so that errors on the generated code point where the
+/// caller expects, instead of at the macro definition:
+///
+/// ```ignore
+/// Spanned(expr, Span::call_site()).to_tokens(&mut tokens);
+/// ```
+pub struct Spanned<T>(pub T, pub Span);
+
+impl<T: ToTokens> ToTokens for Spanned<T> {
+    fn to_tokens(&self, tokens: &mut Tokens) {
+        let mut inner = Tokens::new();
+        self.0.to_tokens(&mut inner);
+        append_with_span(tokens, inner, self.1);
+    }
+}
+
+fn append_with_span(tokens: &mut Tokens, to_append: Tokens, span: Span) {
+    let stream: TokenStream = to_append.into();
+    for tree in stream {
+        tokens.append(respan_tree(tree, span));
+    }
+}
+
+fn respan_tree(tree: TokenTree, span: Span) -> TokenTree {
+    let kind = match tree.kind {
+        TokenNode::Group(delim, stream) => TokenNode::Group(delim, respan_stream(stream, span)),
+        other => other,
+    };
+    TokenTree {
+        span: span,
+        kind: kind,
+    }
+}
+
+fn respan_stream(stream: TokenStream, span: Span) -> TokenStream {
+    stream.into_iter().map(|tree| respan_tree(tree, span)).collect()
+}
+
 impl ToTokens for Term {
     fn to_tokens(&self, tokens: &mut Tokens) {
         tokens.append(tt(TokenNode::Term(*self)));
     }
 }
 
+/// A lifetime, e.g. `'a`, for interpolation into generated generic bounds
+/// and references.
+///
+/// Interpolating a lifetime through `Term` alone produces the wrong token
+/// structure, since a lifetime is an apostrophe joined to an identifier
+/// rather than a plain identifier.
+pub struct Lifetime {
+    name: Term,
+}
+
+impl Lifetime {
+    /// Creates a new `Lifetime` from its name, without the leading `'`, e.g.
+    /// `Lifetime::new("a")` for `'a`.
+    ///
+    /// Panics if `name` is not a valid identifier (empty, starting with a
+    /// digit, or containing a character other than a letter, digit, or
+    /// underscore) or if it itself begins with `'`.
+    pub fn new(name: &str) -> Self {
+        let mut chars = name.chars();
+        let valid = match chars.next() {
+            Some(c) => (c == '_' || c.is_alphabetic()) &&
+                chars.all(|c| c == '_' || c.is_alphanumeric()),
+            None => false,
+        };
+        if !valid {
+            panic!("{:?} is not a valid lifetime name", name);
+        }
+        Lifetime { name: Term::intern(name) }
+    }
+}
+
+impl ToTokens for Lifetime {
+    fn to_tokens(&self, tokens: &mut Tokens) {
+        tokens.append(tt(TokenNode::Op('\'', Spacing::Joint)));
+        tokens.append(tt(TokenNode::Term(self.name)));
+    }
+}
+
 impl ToTokens for str {
     fn to_tokens(&self, tokens: &mut Tokens) {
         tokens.append(tt(TokenNode::Literal(Literal::string(self))));
@@ -132,6 +260,87 @@ impl<'a> ToTokens for ByteStr<'a> {
     }
 }
 
+// Builds a single `Literal` token out of arbitrary literal text, e.g. the
+// `"0xff"` produced by the radix wrappers below, which `Literal` has no
+// dedicated constructor for.
+fn literal_tt(text: String) -> TokenTree {
+    let mut tokens = text.parse::<TokenStream>()
+        .expect("not a valid literal")
+        .into_iter();
+    let tt = tokens.next().expect("empty literal");
+    debug_assert!(tokens.next().is_none(), "expected a single literal token");
+    tt
+}
+
+/// Wrap an unsigned integer so it interpolates as a hexadecimal literal:
+/// `0xff`.
+#[derive(Debug)]
+pub struct Hex<T>(pub T);
+
+/// Wrap an unsigned integer so it interpolates as an octal literal: `0o777`.
+#[derive(Debug)]
+pub struct Oct<T>(pub T);
+
+/// Wrap an unsigned integer so it interpolates as a binary literal: `0b1010`.
+#[derive(Debug)]
+pub struct Bin<T>(pub T);
+
+macro_rules! radix_impls {
+    ($name:ident, $fmt:expr => $($t:ident)*) => ($(
+        impl ToTokens for $name<$t> {
+            fn to_tokens(&self, tokens: &mut Tokens) {
+                tokens.append(literal_tt(format!($fmt, self.0)));
+            }
+        }
+    )*)
+}
+
+// Signed types are deliberately excluded: `format!("{:#x}", ...)` on a
+// negative value prints its two's-complement bit pattern (e.g. `0xff` for
+// `-1i8`), which would silently change the interpolated value rather than
+// just its radix.
+radix_impls! { Hex, "{:#x}" =>
+    u8 u16 u32 u64 usize
+}
+
+radix_impls! { Oct, "{:#o}" =>
+    u8 u16 u32 u64 usize
+}
+
+radix_impls! { Bin, "{:#b}" =>
+    u8 u16 u32 u64 usize
+}
+
+/// Wrap an integer so it interpolates without a type suffix, e.g. `0` rather
+/// than `0i32`.
+#[derive(Debug)]
+pub struct Unsuffixed<T>(pub T);
+
+macro_rules! unsuffixed_impls {
+    ($($t:ident)*) => ($(
+        impl ToTokens for Unsuffixed<$t> {
+            fn to_tokens(&self, tokens: &mut Tokens) {
+                // `literal_tt` parses its argument as a single token, but
+                // `to_string()` on a negative value produces two tokens
+                // (`-` followed by the magnitude), so split the sign off
+                // and emit it as its own `Op` rather than handing the whole
+                // string to the literal parser.
+                let mut repr = self.0.to_string();
+                if repr.starts_with('-') {
+                    repr.remove(0);
+                    tokens.append(tt(TokenNode::Op('-', Spacing::Alone)));
+                }
+                tokens.append(literal_tt(repr));
+            }
+        }
+    )*)
+}
+
+unsuffixed_impls! {
+    i8 i16 i32 i64 isize
+    u8 u16 u32 u64 usize
+}
+
 impl<T: ToTokens> ToTokens for [T] {
     fn to_tokens(&self, tokens: &mut Tokens) {
         let mut sub = Tokens::new();